@@ -41,6 +41,58 @@ impl From<CellState> for u8 {
     }
 }
 
+/// A join-semilattice: a partial order with a bottom element and a
+/// least-upper-bound (`join`) that is idempotent, commutative, and
+/// associative.
+///
+/// Used so that when several neighbors concurrently propose a next state
+/// for the same cell, their proposals can be combined with `join` instead
+/// of picked by last-writer-wins: because `join` is monotone and confluent,
+/// folding proposals in any order yields the same result, which makes
+/// multi-threaded asynchronous stepping safe and deterministic.
+pub trait Merge: Copy + PartialEq {
+    /// The bottom (identity) element of the lattice: `x.join(bottom()) == x`.
+    fn bottom() -> Self;
+
+    /// Idempotent, commutative, associative least-upper-bound.
+    fn join(self, other: Self) -> Self;
+
+    fn is_bottom(self) -> bool {
+        self == Self::bottom()
+    }
+
+    fn is_top(self) -> bool;
+}
+
+impl CellState {
+    /// Rank in the fixed partial order `Void ⊑ Sensor ⊑ Energy ⊑ Compute ⊑
+    /// Structural` used by [`Merge`]. Deliberately independent of the
+    /// `#[repr(u8)]` discriminant, which instead reflects serialization order.
+    fn lattice_rank(self) -> u8 {
+        match self {
+            CellState::Void => 0,
+            CellState::Sensor => 1,
+            CellState::Energy => 2,
+            CellState::Compute => 3,
+            CellState::Structural => 4,
+        }
+    }
+}
+
+impl Merge for CellState {
+    fn bottom() -> Self {
+        CellState::Void
+    }
+
+    fn join(self, other: Self) -> Self {
+        if self.lattice_rank() >= other.lattice_rank() { self } else { other }
+    }
+
+    fn is_top(self) -> bool {
+        self == CellState::Structural
+    }
+}
+
 /// 3D lattice dimensions
 #[derive(Debug, Clone, Copy)]
 pub struct Lattice3D {
@@ -62,6 +114,16 @@ impl Lattice3D {
         z * (self.width * self.height) + y * self.width + x
     }
 
+    /// Inverse of [`Lattice3D::index`].
+    pub fn coords(&self, idx: usize) -> (usize, usize, usize) {
+        let plane = self.width * self.height;
+        let z = idx / plane;
+        let rem = idx % plane;
+        let y = rem / self.width;
+        let x = rem % self.width;
+        (x, y, z)
+    }
+
     /// Get Moore neighborhood (26 neighbors in 3D)
     pub fn moore_neighbors(&self, x: usize, y: usize, z: usize) -> Vec<usize> {
         let mut neighbors = Vec::with_capacity(26);
@@ -89,28 +151,141 @@ impl Lattice3D {
         
         neighbors
     }
+
+    /// Moore neighborhood (every cell of the cube within Chebyshev distance
+    /// `radius`) at an arbitrary radius, excluding the center cell.
+    pub fn moore_neighbors_radius(&self, x: usize, y: usize, z: usize, radius: usize) -> Vec<usize> {
+        let r = radius as isize;
+        let mut neighbors = Vec::new();
+
+        for dz in -r..=r {
+            for dy in -r..=r {
+                for dx in -r..=r {
+                    if dx == 0 && dy == 0 && dz == 0 {
+                        continue;
+                    }
+
+                    let nx = x as isize + dx;
+                    let ny = y as isize + dy;
+                    let nz = z as isize + dz;
+
+                    if nx >= 0 && nx < self.width as isize
+                        && ny >= 0 && ny < self.height as isize
+                        && nz >= 0 && nz < self.depth as isize
+                    {
+                        neighbors.push(self.index(nx as usize, ny as usize, nz as usize));
+                    }
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    /// Von Neumann neighborhood (Manhattan-distance ball) at an arbitrary radius,
+    /// excluding the center cell.
+    pub fn von_neumann_neighbors(&self, x: usize, y: usize, z: usize, radius: usize) -> Vec<usize> {
+        let r = radius as isize;
+        let mut neighbors = Vec::new();
+
+        for dz in -r..=r {
+            for dy in -r..=r {
+                for dx in -r..=r {
+                    let manhattan = dx.abs() + dy.abs() + dz.abs();
+                    if manhattan == 0 || manhattan > r {
+                        continue;
+                    }
+
+                    let nx = x as isize + dx;
+                    let ny = y as isize + dy;
+                    let nz = z as isize + dz;
+
+                    if nx >= 0 && nx < self.width as isize
+                        && ny >= 0 && ny < self.height as isize
+                        && nz >= 0 && nz < self.depth as isize
+                    {
+                        neighbors.push(self.index(nx as usize, ny as usize, nz as usize));
+                    }
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    /// Neighbors selected by a [`Neighborhood`] (shape plus radius).
+    pub fn neighbors(&self, x: usize, y: usize, z: usize, neighborhood: Neighborhood) -> Vec<usize> {
+        match neighborhood.kind {
+            NeighborhoodKind::Moore => self.moore_neighbors_radius(x, y, z, neighborhood.radius),
+            NeighborhoodKind::VonNeumann => self.von_neumann_neighbors(x, y, z, neighborhood.radius),
+        }
+    }
 }
 
-/// Graph-based CA using adjacency lists
+/// Graph-based CA using a compressed-sparse-row (CSR) adjacency.
+///
+/// Edges are staged into a per-node `HashMap` via [`GraphCA::add_edge`] and
+/// then compiled into two flat arrays with [`GraphCA::finalize`]: `offsets`
+/// (length `num_nodes + 1`) and `targets` (all neighbor indices back to
+/// back). `get_neighbors` becomes a zero-allocation slice into `targets`,
+/// and the stepping loop walks both arrays linearly instead of hashing into
+/// a `HashMap` for every node.
 pub struct GraphCA {
-    pub adjacency: HashMap<usize, Vec<usize>>,
     pub states: Vec<u8>,
+    staging: HashMap<usize, Vec<usize>>,
+    offsets: Vec<u32>,
+    targets: Vec<u32>,
 }
 
 impl GraphCA {
     pub fn new(num_nodes: usize) -> Self {
         Self {
-            adjacency: HashMap::new(),
             states: vec![0; num_nodes],
+            staging: HashMap::new(),
+            offsets: vec![0; num_nodes + 1],
+            targets: Vec::new(),
         }
     }
 
+    /// Stage an edge. Takes effect once [`GraphCA::finalize`] is called.
     pub fn add_edge(&mut self, from: usize, to: usize) {
-        self.adjacency.entry(from).or_insert_with(Vec::new).push(to);
+        self.staging.entry(from).or_default().push(to);
     }
 
-    pub fn get_neighbors(&self, node: usize) -> &[usize] {
-        self.adjacency.get(&node).map(|v| v.as_slice()).unwrap_or(&[])
+    /// Compile the staged edge list into CSR form via a counting-sort pass:
+    /// count degrees into `offsets`, prefix-sum them in place, then scatter
+    /// each staged edge into its slot in `targets`.
+    pub fn finalize(&mut self) {
+        let num_nodes = self.states.len();
+        let mut offsets = vec![0u32; num_nodes + 1];
+        for (&from, tos) in &self.staging {
+            offsets[from + 1] += tos.len() as u32;
+        }
+        for i in 0..num_nodes {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut targets = vec![0u32; offsets[num_nodes] as usize];
+        let mut cursor = offsets.clone();
+        for (&from, tos) in &self.staging {
+            for &to in tos {
+                targets[cursor[from] as usize] = to as u32;
+                cursor[from] += 1;
+            }
+        }
+
+        self.offsets = offsets;
+        self.targets = targets;
+    }
+
+    /// Neighbors of `node` as a contiguous, zero-allocation slice.
+    ///
+    /// Reflects the adjacency as of the last [`GraphCA::finalize`] call;
+    /// edges staged afterwards are not visible until `finalize` runs again.
+    pub fn get_neighbors(&self, node: usize) -> &[u32] {
+        let start = self.offsets[node] as usize;
+        let end = self.offsets[node + 1] as usize;
+        &self.targets[start..end]
     }
 }
 
@@ -142,25 +317,352 @@ pub fn step_lattice_3d(
     next_states
 }
 
+/// A bitset used to dedupe worklist enqueues in [`step_lattice_3d_incremental`].
+struct Worklist {
+    bits: Vec<u64>,
+    queue: Vec<usize>,
+}
+
+impl Worklist {
+    fn new(num_cells: usize) -> Self {
+        Self {
+            bits: vec![0u64; num_cells.div_ceil(64)],
+            queue: Vec::new(),
+        }
+    }
+
+    fn enqueue(&mut self, idx: usize) {
+        let word = idx / 64;
+        let bit = idx % 64;
+        if self.bits[word] & (1 << bit) == 0 {
+            self.bits[word] |= 1 << bit;
+            self.queue.push(idx);
+        }
+    }
+}
+
+/// Worklist-driven incremental step for [`step_lattice_3d`]: only cells in
+/// `active` are evaluated, so quiescent regions (e.g. mostly-void lattices)
+/// are skipped entirely instead of re-evaluating every cell every tick.
+///
+/// For any rule where a cell surrounded entirely by void stays void, this
+/// produces a bit-identical result to a full synchronous `step_lattice_3d`
+/// restricted to `active`'s closure, but touches only the live frontier.
+/// Returns the new state vector plus the active set for the next tick: a
+/// cell is carried forward only when its state actually changed, along with
+/// all of its neighbors.
+pub fn step_lattice_3d_incremental(
+    lattice: &Lattice3D,
+    states: &[u8],
+    active: &[usize],
+    rule: OuterTotalisticRule,
+) -> (Vec<u8>, Vec<usize>) {
+    let mut next_states = states.to_vec();
+
+    let mut worklist = Worklist::new(states.len());
+    for &idx in active {
+        worklist.enqueue(idx);
+    }
+
+    let mut next_active = Worklist::new(states.len());
+    for &idx in &worklist.queue {
+        let (x, y, z) = lattice.coords(idx);
+        let neighbors = lattice.moore_neighbors(x, y, z);
+        let neighbor_count = neighbors.iter()
+            .filter(|&&n| states[n] != 0)
+            .count();
+
+        let next_state = rule(states[idx], neighbor_count);
+        next_states[idx] = next_state;
+
+        if next_state != states[idx] {
+            next_active.enqueue(idx);
+            for &n in &neighbors {
+                next_active.enqueue(n);
+            }
+        }
+    }
+
+    (next_states, next_active.queue)
+}
+
+/// A 3D summed-volume table (integral image) over a boolean "active" mask.
+///
+/// Built once per tick in O(N) via the standard inclusion-exclusion
+/// recurrence; afterwards the active-cell count inside any axis-aligned box
+/// is an O(1) eight-corner lookup, which is what lets
+/// [`step_lattice_totalistic`] support radius-`r` neighborhoods in O(N)
+/// instead of O(N * r^3).
+struct SummedVolume {
+    lattice: Lattice3D,
+    sums: Vec<u64>,
+}
+
+impl SummedVolume {
+    /// Build `S` where `S[x,y,z]` is the sum of `active` flags over the
+    /// sub-box `[0..=x, 0..=y, 0..=z]`.
+    fn build(lattice: &Lattice3D, active: &[bool]) -> Self {
+        let mut sums = vec![0u64; active.len()];
+
+        for z in 0..lattice.depth {
+            for y in 0..lattice.height {
+                for x in 0..lattice.width {
+                    let idx = lattice.index(x, y, z);
+                    let a = active[idx] as u64;
+
+                    let sx = if x > 0 { sums[lattice.index(x - 1, y, z)] } else { 0 };
+                    let sy = if y > 0 { sums[lattice.index(x, y - 1, z)] } else { 0 };
+                    let sz = if z > 0 { sums[lattice.index(x, y, z - 1)] } else { 0 };
+                    let sxy = if x > 0 && y > 0 { sums[lattice.index(x - 1, y - 1, z)] } else { 0 };
+                    let sxz = if x > 0 && z > 0 { sums[lattice.index(x - 1, y, z - 1)] } else { 0 };
+                    let syz = if y > 0 && z > 0 { sums[lattice.index(x, y - 1, z - 1)] } else { 0 };
+                    let sxyz = if x > 0 && y > 0 && z > 0 {
+                        sums[lattice.index(x - 1, y - 1, z - 1)]
+                    } else {
+                        0
+                    };
+
+                    sums[idx] = a + sx + sy + sz - sxy - sxz - syz + sxyz;
+                }
+            }
+        }
+
+        Self { lattice: *lattice, sums }
+    }
+
+    /// `S` at `(x, y, z)`, with negative coordinates (out-of-range lows)
+    /// treated as 0 and positive overflow clamped to the lattice bounds.
+    fn corner(&self, x: isize, y: isize, z: isize) -> u64 {
+        if x < 0 || y < 0 || z < 0 {
+            return 0;
+        }
+        let x = (x as usize).min(self.lattice.width - 1);
+        let y = (y as usize).min(self.lattice.height - 1);
+        let z = (z as usize).min(self.lattice.depth - 1);
+        self.sums[self.lattice.index(x, y, z)]
+    }
+
+    /// Active-cell count inside the axis-aligned box `[x0..=x1] x [y0..=y1] x
+    /// [z0..=z1]`, via the eight-corner inclusion-exclusion formula. Box
+    /// corners are clamped to the lattice bounds, with out-of-range lows
+    /// treated as 0 so edge and corner cells see a correctly truncated box.
+    fn count_box(&self, x0: isize, x1: isize, y0: isize, y1: isize, z0: isize, z1: isize) -> u64 {
+        let total = self.corner(x1, y1, z1) as i64;
+        let a = self.corner(x0 - 1, y1, z1) as i64;
+        let b = self.corner(x1, y0 - 1, z1) as i64;
+        let c = self.corner(x1, y1, z0 - 1) as i64;
+        let ab = self.corner(x0 - 1, y0 - 1, z1) as i64;
+        let ac = self.corner(x0 - 1, y1, z0 - 1) as i64;
+        let bc = self.corner(x1, y0 - 1, z0 - 1) as i64;
+        let abc = self.corner(x0 - 1, y0 - 1, z0 - 1) as i64;
+        (total - a - b - c + ab + ac + bc - abc) as u64
+    }
+}
+
+/// Step a lattice with an outer-totalistic rule over a radius-`r` box count
+/// (not just the 26-cell Moore shell), backed by a [`SummedVolume`] so the
+/// whole step stays O(N) instead of O(N * r^3). The count passed to `rule`
+/// excludes the center cell, whose state is passed separately.
+pub fn step_lattice_totalistic(
+    lattice: &Lattice3D,
+    states: &[u8],
+    radius: usize,
+    rule: OuterTotalisticRule,
+) -> Vec<u8> {
+    let active: Vec<bool> = states.iter().map(|&s| s != 0).collect();
+    let volume = SummedVolume::build(lattice, &active);
+    let r = radius as isize;
+
+    let mut next_states = vec![0; states.len()];
+    for z in 0..lattice.depth {
+        for y in 0..lattice.height {
+            for x in 0..lattice.width {
+                let idx = lattice.index(x, y, z);
+                let (xi, yi, zi) = (x as isize, y as isize, z as isize);
+
+                let box_count = volume.count_box(xi - r, xi + r, yi - r, yi + r, zi - r, zi + r);
+                let neighbor_count = box_count as usize - active[idx] as usize;
+
+                next_states[idx] = rule(states[idx], neighbor_count);
+            }
+        }
+    }
+
+    next_states
+}
+
 /// Step the CA on an arbitrary graph
+///
+/// Iterates the CSR `offsets`/`targets` arrays directly so the hot loop is a
+/// single linear scan with no hashing or per-node allocation.
 pub fn step_graph(
     graph: &GraphCA,
     rule: OuterTotalisticRule,
 ) -> Vec<u8> {
     let mut next_states = vec![0; graph.states.len()];
-    
-    for node in 0..graph.states.len() {
+
+    for (node, next) in next_states.iter_mut().enumerate() {
         let neighbors = graph.get_neighbors(node);
         let neighbor_count = neighbors.iter()
-            .filter(|&&n| graph.states[n] != 0)
+            .filter(|&&n| graph.states[n as usize] != 0)
             .count();
-        
-        next_states[node] = rule(graph.states[node], neighbor_count);
+
+        *next = rule(graph.states[node], neighbor_count);
     }
-    
+
     next_states
 }
 
+/// Asynchronous step over a [`GraphCA`]'s adjacency: each node proposes its
+/// own current state to every neighbor, and a cell's next state is the
+/// [`Merge::join`] of all proposals it receives plus its own current state.
+///
+/// Because `join` is idempotent, commutative, and associative, the result
+/// does not depend on the order proposals are folded in, so this is safe to
+/// drive from multiple writer threads without coordination beyond the join.
+pub fn step_graph_async<T: Merge>(graph: &GraphCA, states: &[T]) -> Vec<T> {
+    let mut next_states = states.to_vec();
+
+    for (node, &current) in states.iter().enumerate() {
+        for &neighbor in graph.get_neighbors(node) {
+            let n = neighbor as usize;
+            next_states[n] = next_states[n].join(current);
+        }
+    }
+
+    next_states
+}
+
+/// Flat union-find (disjoint-set forest) with path compression and
+/// union-by-rank, used by [`label_components`].
+struct UnionFind {
+    parent: Vec<u32>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n as u32).collect(),
+            rank: vec![0; n],
+        }
+    }
+
+    fn find(&mut self, x: u32) -> u32 {
+        if self.parent[x as usize] != x {
+            self.parent[x as usize] = self.find(self.parent[x as usize]);
+        }
+        self.parent[x as usize]
+    }
+
+    fn union(&mut self, a: u32, b: u32) {
+        let ra = self.find(a);
+        let rb = self.find(b);
+        if ra == rb {
+            return;
+        }
+        match self.rank[ra as usize].cmp(&self.rank[rb as usize]) {
+            std::cmp::Ordering::Less => self.parent[ra as usize] = rb,
+            std::cmp::Ordering::Greater => self.parent[rb as usize] = ra,
+            std::cmp::Ordering::Equal => {
+                self.parent[rb as usize] = ra;
+                self.rank[ra as usize] += 1;
+            }
+        }
+    }
+}
+
+/// Adjacency source for [`label_components`]: either a [`Lattice3D`] (Moore
+/// neighbors) or a [`GraphCA`] (CSR adjacency).
+pub enum Adjacency<'a> {
+    Lattice(&'a Lattice3D),
+    Graph(&'a GraphCA),
+}
+
+impl Adjacency<'_> {
+    fn neighbors(&self, idx: usize) -> Vec<usize> {
+        match self {
+            Adjacency::Lattice(lattice) => {
+                let (x, y, z) = lattice.coords(idx);
+                lattice.moore_neighbors(x, y, z)
+            }
+            Adjacency::Graph(graph) => {
+                graph.get_neighbors(idx).iter().map(|&n| n as usize).collect()
+            }
+        }
+    }
+}
+
+/// Summary of one connected component found by [`label_components`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentSummary {
+    pub label: u32,
+    pub size: usize,
+    pub dominant_state: CellState,
+}
+
+/// Label the connected components formed by active (`!= Void`) cells.
+///
+/// Runs a flat union-find over the `states.len()` domain, unioning each
+/// active cell with each active neighbor in a single pass (Moore adjacency
+/// for a [`Lattice3D`], CSR adjacency for a [`GraphCA`]), then compacts the
+/// resulting roots into dense `0..n_components` labels. Returns a label per
+/// cell (`u32::MAX` for inactive cells, so labels stay aligned with
+/// `states`) plus a size/dominant-state summary per component. Useful for
+/// detecting when a self-assembled structure fragments or two clusters
+/// merge between ticks.
+pub fn label_components(adjacency: Adjacency, states: &[CellState]) -> (Vec<u32>, Vec<ComponentSummary>) {
+    let n = states.len();
+    let mut uf = UnionFind::new(n);
+
+    for idx in 0..n {
+        if states[idx] == CellState::Void {
+            continue;
+        }
+        for neighbor in adjacency.neighbors(idx) {
+            if states[neighbor] != CellState::Void {
+                uf.union(idx as u32, neighbor as u32);
+            }
+        }
+    }
+
+    let mut root_to_label: HashMap<u32, u32> = HashMap::new();
+    let mut labels = vec![u32::MAX; n];
+    let mut counts: Vec<(usize, [usize; 5])> = Vec::new();
+
+    for idx in 0..n {
+        if states[idx] == CellState::Void {
+            continue;
+        }
+        let root = uf.find(idx as u32);
+        let label = *root_to_label.entry(root).or_insert_with(|| {
+            counts.push((0, [0usize; 5]));
+            (counts.len() - 1) as u32
+        });
+        labels[idx] = label;
+
+        let entry = &mut counts[label as usize];
+        entry.0 += 1;
+        entry.1[states[idx] as usize] += 1;
+    }
+
+    let summaries = counts
+        .into_iter()
+        .enumerate()
+        .map(|(label, (size, state_counts))| {
+            let dominant_state = state_counts
+                .iter()
+                .enumerate()
+                .max_by_key(|&(_, &count)| count)
+                .map(|(state, _)| CellState::from(state as u8))
+                .unwrap_or(CellState::Void);
+            ComponentSummary { label: label as u32, size, dominant_state }
+        })
+        .collect();
+
+    (labels, summaries)
+}
+
 /// Example rule: Conway's Game of Life adapted for 3D
 pub fn conway_3d_rule(current: u8, neighbor_count: usize) -> u8 {
     match (current, neighbor_count) {
@@ -170,6 +672,238 @@ pub fn conway_3d_rule(current: u8, neighbor_count: usize) -> u8 {
     }
 }
 
+/// Neighborhood shape used when evaluating a [`Rule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeighborhoodKind {
+    Moore,
+    VonNeumann,
+}
+
+/// Neighborhood selector for a [`Rule`]: shape plus radius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neighborhood {
+    pub kind: NeighborhoodKind,
+    pub radius: usize,
+}
+
+impl Default for Neighborhood {
+    fn default() -> Self {
+        Self { kind: NeighborhoodKind::Moore, radius: 1 }
+    }
+}
+
+/// Comparator used by a [`NeighborCondition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparator {
+    AtLeast,
+    AtMost,
+    Exactly,
+}
+
+/// A single condition on the count of one neighbor state, e.g. `>=2 Energy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NeighborCondition {
+    pub state: CellState,
+    pub comparator: Comparator,
+    pub count: usize,
+}
+
+impl NeighborCondition {
+    fn matches(&self, counts: &[usize; 5]) -> bool {
+        let n = counts[self.state as usize];
+        match self.comparator {
+            Comparator::AtLeast => n >= self.count,
+            Comparator::AtMost => n <= self.count,
+            Comparator::Exactly => n == self.count,
+        }
+    }
+}
+
+/// A state transition that fires when every [`NeighborCondition`] holds.
+#[derive(Debug, Clone)]
+pub struct TransitionClause {
+    pub from: CellState,
+    pub to: CellState,
+    pub conditions: Vec<NeighborCondition>,
+}
+
+/// A parseable, multi-state CA rule modeled on extended life notation.
+///
+/// `birth`/`survive` hold the classic `B4-7/S4-7`-style ranges over the total
+/// non-void neighbor count and act as a fallback (birth into [`CellState::Structural`],
+/// survival of the current state). `clauses` are checked first, in order, and
+/// can key on counts of *specific* neighbor states, e.g. "born Compute when
+/// at least 2 Energy neighbors and at least 3 Structural". `neighborhood`
+/// picks the shape (Moore or von Neumann) and radius lattice stepping uses.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub neighborhood: Neighborhood,
+    pub clauses: Vec<TransitionClause>,
+    pub birth: Vec<(usize, usize)>,
+    pub survive: Vec<(usize, usize)>,
+}
+
+impl Rule {
+    /// Parse a rule spec: `;`-separated terms, each either a `B.../S...`
+    /// range pair or a clause of the form `born <State> when <cond> [and
+    /// <cond>]*` or `<State> -> <State> when <cond> [and <cond>]*`, where
+    /// `<cond>` is `<cmp><count> <State>` with `<cmp>` one of `>=`, `<=`, `=`.
+    pub fn parse(spec: &str, neighborhood: Neighborhood) -> Result<Rule, String> {
+        let mut birth = Vec::new();
+        let mut survive = Vec::new();
+        let mut clauses = Vec::new();
+
+        for term in spec.split(';') {
+            let term = term.trim();
+            if term.is_empty() {
+                continue;
+            }
+
+            if term.to_ascii_lowercase().starts_with('b') && term.contains('/') {
+                let mut halves = term.splitn(2, '/');
+                let b = halves.next().ok_or("missing birth half")?;
+                let s = halves.next().ok_or("missing survive half")?;
+                birth.extend(parse_ranges(b)?);
+                survive.extend(parse_ranges(s)?);
+            } else {
+                clauses.push(parse_clause(term)?);
+            }
+        }
+
+        Ok(Rule { neighborhood, clauses, birth, survive })
+    }
+
+    /// Evaluate the rule for a cell currently in `current`, given a count of
+    /// neighbors in each [`CellState`] (indexed by `CellState as usize`).
+    pub fn apply(&self, current: CellState, counts: &[usize; 5]) -> CellState {
+        for clause in &self.clauses {
+            if clause.from == current && clause.conditions.iter().all(|c| c.matches(counts)) {
+                return clause.to;
+            }
+        }
+
+        let total_alive: usize = counts[1..].iter().sum();
+        match current {
+            CellState::Void => {
+                if self.birth.iter().any(|&(lo, hi)| total_alive >= lo && total_alive <= hi) {
+                    CellState::Structural
+                } else {
+                    CellState::Void
+                }
+            }
+            other => {
+                if self.survive.iter().any(|&(lo, hi)| total_alive >= lo && total_alive <= hi) {
+                    other
+                } else {
+                    CellState::Void
+                }
+            }
+        }
+    }
+}
+
+fn parse_ranges(text: &str) -> Result<Vec<(usize, usize)>, String> {
+    let digits_from = text.find(|c: char| c.is_ascii_digit());
+    let Some(start) = digits_from else {
+        return Ok(Vec::new());
+    };
+    text[start..]
+        .split(',')
+        .map(|part| {
+            let part = part.trim();
+            if let Some((lo, hi)) = part.split_once('-') {
+                let lo = lo.trim().parse::<usize>().map_err(|e| e.to_string())?;
+                let hi = hi.trim().parse::<usize>().map_err(|e| e.to_string())?;
+                Ok((lo, hi))
+            } else {
+                let n = part.parse::<usize>().map_err(|e| e.to_string())?;
+                Ok((n, n))
+            }
+        })
+        .collect()
+}
+
+fn parse_state(name: &str) -> Result<CellState, String> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "void" => Ok(CellState::Void),
+        "structural" => Ok(CellState::Structural),
+        "compute" => Ok(CellState::Compute),
+        "energy" => Ok(CellState::Energy),
+        "sensor" => Ok(CellState::Sensor),
+        other => Err(format!("unknown cell state '{other}'")),
+    }
+}
+
+fn parse_condition(text: &str) -> Result<NeighborCondition, String> {
+    let text = text.trim();
+    let (comparator, rest) = if let Some(rest) = text.strip_prefix(">=") {
+        (Comparator::AtLeast, rest)
+    } else if let Some(rest) = text.strip_prefix("<=") {
+        (Comparator::AtMost, rest)
+    } else if let Some(rest) = text.strip_prefix('=') {
+        (Comparator::Exactly, rest)
+    } else {
+        return Err(format!("condition '{text}' missing a >=, <= or = comparator"));
+    };
+
+    let rest = rest.trim();
+    let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let count = rest[..digits_end].parse::<usize>().map_err(|e| e.to_string())?;
+    let state = parse_state(&rest[digits_end..])?;
+
+    Ok(NeighborCondition { state, comparator, count })
+}
+
+fn parse_clause(term: &str) -> Result<TransitionClause, String> {
+    let (heading, conditions_text) = term
+        .split_once("when")
+        .ok_or_else(|| format!("clause '{term}' missing 'when'"))?;
+    let heading = heading.trim();
+
+    let (from, to) = if let Some(rest) = heading.strip_prefix("born") {
+        (CellState::Void, parse_state(rest)?)
+    } else if let Some((from, to)) = heading.split_once("->") {
+        (parse_state(from)?, parse_state(to)?)
+    } else {
+        return Err(format!("clause heading '{heading}' is neither 'born <State>' nor '<State> -> <State>'"));
+    };
+
+    let conditions = conditions_text
+        .split(" and ")
+        .map(parse_condition)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(TransitionClause { from, to, conditions })
+}
+
+/// Step a 3D lattice using a [`Rule`], reading the neighborhood shape and
+/// radius from the rule itself rather than always using the 26-cell Moore shell.
+pub fn step_lattice_3d_rule(
+    lattice: &Lattice3D,
+    states: &[CellState],
+    rule: &Rule,
+) -> Vec<CellState> {
+    let mut next_states = vec![CellState::Void; states.len()];
+
+    for z in 0..lattice.depth {
+        for y in 0..lattice.height {
+            for x in 0..lattice.width {
+                let idx = lattice.index(x, y, z);
+                let neighbors = lattice.neighbors(x, y, z, rule.neighborhood);
+
+                let mut counts = [0usize; 5];
+                for n in neighbors {
+                    counts[states[n] as usize] += 1;
+                }
+
+                next_states[idx] = rule.apply(states[idx], &counts);
+            }
+        }
+    }
+
+    next_states
+}
+
 #[cfg(feature = "python")]
 #[pymodule]
 fn uft_ca(_py: Python, m: &PyModule) -> PyResult<()> {
@@ -234,6 +968,46 @@ mod tests {
         assert_eq!(next.len(), states.len());
     }
 
+    #[test]
+    fn test_step_lattice_incremental_matches_full_step() {
+        let lattice = Lattice3D::new(3, 3, 3);
+        let mut states = vec![0; lattice.size()];
+
+        states[lattice.index(1, 1, 1)] = 1;
+        states[lattice.index(0, 1, 1)] = 1;
+        states[lattice.index(2, 1, 1)] = 1;
+        states[lattice.index(1, 0, 1)] = 1;
+        states[lattice.index(1, 2, 1)] = 1;
+
+        let full = step_lattice_3d(&lattice, &states, conway_3d_rule);
+
+        let active: Vec<usize> = (0..states.len())
+            .filter(|&i| states[i] != 0)
+            .flat_map(|i| {
+                let (x, y, z) = lattice.coords(i);
+                let mut ns = lattice.moore_neighbors(x, y, z);
+                ns.push(i);
+                ns
+            })
+            .collect();
+        let (incremental, _next_active) =
+            step_lattice_3d_incremental(&lattice, &states, &active, conway_3d_rule);
+
+        assert_eq!(incremental, full);
+    }
+
+    #[test]
+    fn test_step_lattice_incremental_skips_quiescent_void() {
+        let lattice = Lattice3D::new(3, 3, 3);
+        let states = vec![0; lattice.size()];
+
+        let (next, next_active) =
+            step_lattice_3d_incremental(&lattice, &states, &[], conway_3d_rule);
+
+        assert_eq!(next, states);
+        assert!(next_active.is_empty());
+    }
+
     #[test]
     fn test_graph_ca() {
         let mut graph = GraphCA::new(5);
@@ -242,18 +1016,267 @@ mod tests {
         graph.add_edge(1, 2);
         graph.add_edge(2, 3);
         graph.add_edge(3, 4);
-        
+        graph.finalize();
+
         graph.states[0] = 1;
         graph.states[1] = 1;
-        
+
         let next = step_graph(&graph, conway_3d_rule);
         assert_eq!(next.len(), 5);
     }
 
+    #[test]
+    fn test_graph_ca_csr_neighbors() {
+        let mut graph = GraphCA::new(5);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(2, 3);
+        graph.finalize();
+
+        assert_eq!(graph.get_neighbors(0), &[1, 2]);
+        assert_eq!(graph.get_neighbors(1), &[]);
+        assert_eq!(graph.get_neighbors(2), &[3]);
+    }
+
     #[test]
     fn test_cell_state_conversion() {
         assert_eq!(CellState::from(0), CellState::Void);
         assert_eq!(CellState::from(1), CellState::Structural);
         assert_eq!(u8::from(CellState::Compute), 2);
     }
+
+    const ALL_STATES: [CellState; 5] = [
+        CellState::Void,
+        CellState::Structural,
+        CellState::Compute,
+        CellState::Energy,
+        CellState::Sensor,
+    ];
+
+    #[test]
+    fn test_merge_commutative() {
+        for &a in &ALL_STATES {
+            for &b in &ALL_STATES {
+                assert_eq!(a.join(b), b.join(a));
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_associative() {
+        for &a in &ALL_STATES {
+            for &b in &ALL_STATES {
+                for &c in &ALL_STATES {
+                    assert_eq!(a.join(b).join(c), a.join(b.join(c)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_idempotent() {
+        for &a in &ALL_STATES {
+            assert_eq!(a.join(a), a);
+        }
+    }
+
+    #[test]
+    fn test_merge_void_is_identity() {
+        for &a in &ALL_STATES {
+            assert_eq!(a.join(CellState::Void), a);
+            assert_eq!(CellState::Void.join(a), a);
+        }
+        assert!(CellState::Void.is_bottom());
+        assert!(CellState::Structural.is_top());
+        assert!(!CellState::Structural.is_bottom());
+    }
+
+    #[test]
+    fn test_step_graph_async_order_independent() {
+        let mut graph = GraphCA::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 0);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.finalize();
+
+        let states = [CellState::Structural, CellState::Void, CellState::Sensor];
+        let next = step_graph_async(&graph, &states);
+
+        // Node 1 receives proposals from both neighbors; the result is the
+        // same regardless of which proposal is folded in first.
+        assert_eq!(next[1], CellState::Structural.join(CellState::Sensor));
+        assert_eq!(next[0], CellState::Structural);
+        assert_eq!(next[2], CellState::Sensor);
+    }
+
+    /// Brute-force reference: count active neighbors in a radius-`r` box,
+    /// excluding the center cell, by scanning the box directly.
+    fn brute_force_box_neighbor_count(lattice: &Lattice3D, states: &[u8], x: usize, y: usize, z: usize, radius: usize) -> usize {
+        let r = radius as isize;
+        let mut count = 0;
+        for dz in -r..=r {
+            for dy in -r..=r {
+                for dx in -r..=r {
+                    if dx == 0 && dy == 0 && dz == 0 {
+                        continue;
+                    }
+                    let nx = x as isize + dx;
+                    let ny = y as isize + dy;
+                    let nz = z as isize + dz;
+                    if nx >= 0 && nx < lattice.width as isize
+                        && ny >= 0 && ny < lattice.height as isize
+                        && nz >= 0 && nz < lattice.depth as isize
+                        && states[lattice.index(nx as usize, ny as usize, nz as usize)] != 0
+                    {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    fn radius2_rule(current: u8, neighbor_count: usize) -> u8 {
+        match (current, neighbor_count) {
+            (0, 10..=40) => 1,
+            (1, 5..=40) => 1,
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn test_step_lattice_totalistic_matches_brute_force() {
+        let lattice = Lattice3D::new(6, 6, 6);
+        let mut states = vec![0u8; lattice.size()];
+        for (i, s) in states.iter_mut().enumerate() {
+            *s = (i % 3 == 0) as u8;
+        }
+
+        let next = step_lattice_totalistic(&lattice, &states, 2, radius2_rule);
+
+        for z in 0..lattice.depth {
+            for y in 0..lattice.height {
+                for x in 0..lattice.width {
+                    let idx = lattice.index(x, y, z);
+                    let expected_count = brute_force_box_neighbor_count(&lattice, &states, x, y, z, 2);
+                    let expected = radius2_rule(states[idx], expected_count);
+                    assert_eq!(next[idx], expected, "mismatch at ({x},{y},{z})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_lattice_totalistic_corner_clamping() {
+        // Radius larger than the lattice: every cell's box must clamp to the
+        // lattice bounds rather than reading out-of-range "ghost" cells.
+        let lattice = Lattice3D::new(3, 3, 3);
+        let states = vec![1u8; lattice.size()];
+
+        let next = step_lattice_totalistic(&lattice, &states, 5, radius2_rule);
+
+        let corner = lattice.index(0, 0, 0);
+        // Corner cell has 26 neighbors in a fully-active 3x3x3 lattice.
+        assert_eq!(next[corner], radius2_rule(1, 26));
+    }
+
+    #[test]
+    fn test_label_components_lattice_two_clusters() {
+        let lattice = Lattice3D::new(5, 1, 1);
+        let mut states = vec![CellState::Void; lattice.size()];
+        states[lattice.index(0, 0, 0)] = CellState::Structural;
+        states[lattice.index(1, 0, 0)] = CellState::Structural;
+        states[lattice.index(3, 0, 0)] = CellState::Compute;
+
+        let (labels, summary) = label_components(Adjacency::Lattice(&lattice), &states);
+
+        assert_eq!(labels[lattice.index(0, 0, 0)], labels[lattice.index(1, 0, 0)]);
+        assert_ne!(labels[lattice.index(0, 0, 0)], labels[lattice.index(3, 0, 0)]);
+        assert_eq!(labels[lattice.index(2, 0, 0)], u32::MAX);
+
+        assert_eq!(summary.len(), 2);
+        let sizes: Vec<usize> = summary.iter().map(|c| c.size).collect();
+        assert!(sizes.contains(&2) && sizes.contains(&1));
+    }
+
+    #[test]
+    fn test_label_components_graph_dominant_state() {
+        let mut graph = GraphCA::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 0);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.finalize();
+
+        let states = [
+            CellState::Structural,
+            CellState::Structural,
+            CellState::Compute,
+            CellState::Void,
+        ];
+        let (labels, summary) = label_components(Adjacency::Graph(&graph), &states);
+
+        assert_eq!(labels[0], labels[1]);
+        assert_eq!(labels[1], labels[2]);
+        assert_eq!(labels[3], u32::MAX);
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].size, 3);
+        assert_eq!(summary[0].dominant_state, CellState::Structural);
+    }
+
+    #[test]
+    fn test_rule_parse_birth_survive() {
+        let rule = Rule::parse("B4-7/S4-7", Neighborhood::default()).unwrap();
+        assert_eq!(rule.birth, vec![(4, 7)]);
+        assert_eq!(rule.survive, vec![(4, 7)]);
+        assert!(rule.clauses.is_empty());
+    }
+
+    #[test]
+    fn test_rule_apply_conway_equivalent() {
+        let rule = Rule::parse("B4-7/S4-7", Neighborhood::default()).unwrap();
+        let mut counts = [0usize; 5];
+        counts[CellState::Structural as usize] = 5;
+
+        assert_eq!(rule.apply(CellState::Void, &counts), CellState::Structural);
+        assert_eq!(rule.apply(CellState::Structural, &counts), CellState::Structural);
+
+        counts[CellState::Structural as usize] = 1;
+        assert_eq!(rule.apply(CellState::Structural, &counts), CellState::Void);
+    }
+
+    #[test]
+    fn test_rule_specific_state_clause() {
+        let rule = Rule::parse(
+            "B4-7/S4-7; born Compute when >=2 Energy and >=3 Structural",
+            Neighborhood::default(),
+        )
+        .unwrap();
+
+        let mut counts = [0usize; 5];
+        counts[CellState::Energy as usize] = 2;
+        counts[CellState::Structural as usize] = 3;
+        assert_eq!(rule.apply(CellState::Void, &counts), CellState::Compute);
+
+        counts[CellState::Structural as usize] = 1;
+        assert_eq!(rule.apply(CellState::Void, &counts), CellState::Void);
+    }
+
+    #[test]
+    fn test_rule_von_neumann_lattice_step() {
+        let lattice = Lattice3D::new(3, 3, 3);
+        let mut states = vec![CellState::Void; lattice.size()];
+        states[lattice.index(1, 1, 1)] = CellState::Structural;
+
+        let rule = Rule::parse(
+            "B1-6/S1-6",
+            Neighborhood { kind: NeighborhoodKind::VonNeumann, radius: 1 },
+        )
+        .unwrap();
+
+        let next = step_lattice_3d_rule(&lattice, &states, &rule);
+        assert_eq!(next[lattice.index(1, 0, 1)], CellState::Structural);
+        assert_eq!(next[lattice.index(0, 0, 0)], CellState::Void);
+    }
 }